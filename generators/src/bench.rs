@@ -0,0 +1,137 @@
+//! Workload-driven benchmark harness for generation throughput.
+//!
+//! A workload file describes a collection, a document count, a batch size, and
+//! field-distribution knobs; the `bench` binary runs it and measures end-to-end
+//! throughput (docs/sec), per-batch insert latency percentiles, and total wall
+//! time. Results are printed as a machine-readable JSON summary and can be
+//! appended to a historical file keyed by git commit so regressions are visible
+//! over time.
+//!
+//! A dry run generates documents in memory without touching Mongo, isolating
+//! generation cost from insert cost; pinning `seed` makes two runs comparable.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::path::Path;
+
+/// A single benchmark workload.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workload {
+    /// Target collection name.
+    pub collection: String,
+    /// Number of documents to generate.
+    pub count: usize,
+    /// Documents per batch (defaults to 100, the historical hardcoded value).
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Optional RNG seed; when set, two runs generate identical documents.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Free-form field-distribution knobs. They are parsed but not read: the
+    /// `bench` binary's document generator ignores them.
+    #[serde(default)]
+    pub knobs: serde_json::Value,
+}
+
+fn default_batch_size() -> usize {
+    100
+}
+
+/// A container of one or more workloads, as stored in a workload file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum WorkloadFile {
+    One(Workload),
+    Many(Vec<Workload>),
+}
+
+impl WorkloadFile {
+    /// Load workloads from a JSON file, accepting either a single object or an
+    /// array of workloads.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Vec<Workload>, Box<dyn Error>> {
+        let raw = std::fs::read_to_string(path)?;
+        Ok(match serde_json::from_str(&raw)? {
+            WorkloadFile::One(w) => vec![w],
+            WorkloadFile::Many(ws) => ws,
+        })
+    }
+}
+
+/// The measured result of running one workload.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub collection: String,
+    pub count: usize,
+    pub batch_size: usize,
+    pub dry_run: bool,
+    pub commit: String,
+    pub wall_time_ms: f64,
+    pub docs_per_sec: f64,
+    pub batch_latency_ms: LatencySummary,
+}
+
+/// Summary statistics over per-batch latencies.
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    pub min: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl LatencySummary {
+    /// Compute min/p50/p90/p99/max from a slice of millisecond samples.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return LatencySummary {
+                min: 0.0,
+                p50: 0.0,
+                p90: 0.0,
+                p99: 0.0,
+                max: 0.0,
+            };
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        LatencySummary {
+            min: sorted[0],
+            p50: percentile(&sorted, 0.50),
+            p90: percentile(&sorted, 0.90),
+            p99: percentile(&sorted, 0.99),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+// Nearest-rank percentile over a pre-sorted slice.
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    let idx = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[idx]
+}
+
+/// Read the current git commit, or `"unknown"` if it cannot be determined.
+pub fn current_commit() -> String {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Append a result to a historical JSON file (an array of [`BenchResult`]s),
+/// creating it if absent.
+pub fn append_history(path: impl AsRef<Path>, result: &BenchResult) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let mut history: Vec<serde_json::Value> = if path.exists() {
+        serde_json::from_str(&std::fs::read_to_string(path)?)?
+    } else {
+        Vec::new()
+    };
+    history.push(serde_json::to_value(result)?);
+    std::fs::write(path, serde_json::to_string_pretty(&history)?)?;
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use clap::Parser;
+use log::{error, info};
+use medapp_generators::bench::{
+    append_history, current_commit, BenchResult, LatencySummary, Workload, WorkloadFile,
+};
+use medapp_generators::{connect_to_mongodb, INITIAL_SCHEMA_VERSION};
+use mongodb::bson::{doc, Document};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+use std::time::Instant;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about = "Benchmark generation throughput against workload files")]
+struct Args {
+    /// Path to a workload JSON file (a single workload or an array).
+    #[clap(long)]
+    workload: String,
+
+    /// Generate documents in memory without inserting into Mongo.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Append each result to this historical JSON file, keyed by git commit.
+    #[clap(long)]
+    history: Option<String>,
+
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+// Build one representative document from a seeded RNG so runs with the same
+// seed are byte-identical.
+fn bench_doc(rng: &mut StdRng) -> Document {
+    doc! {
+        "field_a": rng.gen_range(0..1_000_000),
+        "field_b": (0..12).map(|_| rng.gen_range(b'a'..=b'z') as char).collect::<String>(),
+        "field_c": rng.gen_bool(0.5),
+        "schema_version": INITIAL_SCHEMA_VERSION,
+    }
+}
+
+async fn run_workload(
+    workload: &Workload,
+    dry_run: bool,
+) -> Result<BenchResult, Box<dyn Error>> {
+    let collection = if dry_run {
+        None
+    } else {
+        let (db, _client) = connect_to_mongodb().await?;
+        Some(db.collection::<Document>(&workload.collection))
+    };
+
+    let mut rng = match workload.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let batch_size = workload.batch_size.max(1);
+    let num_batches = workload.count.div_ceil(batch_size);
+    let mut latencies = Vec::with_capacity(num_batches);
+
+    info!(
+        "Running workload '{}': {} docs, batch size {}{}",
+        workload.collection,
+        workload.count,
+        batch_size,
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    let wall_start = Instant::now();
+    for batch_idx in 0..num_batches {
+        let current = std::cmp::min(batch_size, workload.count - batch_idx * batch_size);
+        let batch: Vec<Document> = (0..current).map(|_| bench_doc(&mut rng)).collect();
+
+        let batch_start = Instant::now();
+        if let Some(collection) = &collection {
+            collection.insert_many(batch, None).await?;
+        }
+        latencies.push(batch_start.elapsed().as_secs_f64() * 1000.0);
+    }
+    let wall = wall_start.elapsed();
+
+    let wall_time_ms = wall.as_secs_f64() * 1000.0;
+    let docs_per_sec = if wall.as_secs_f64() > 0.0 {
+        workload.count as f64 / wall.as_secs_f64()
+    } else {
+        0.0
+    };
+
+    Ok(BenchResult {
+        collection: workload.collection.clone(),
+        count: workload.count,
+        batch_size,
+        dry_run,
+        commit: current_commit(),
+        wall_time_ms,
+        docs_per_sec,
+        batch_latency_ms: LatencySummary::from_samples(&latencies),
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    medapp_generators::setup_logger(args.verbose);
+
+    let workloads = WorkloadFile::from_file(&args.workload)?;
+    if workloads.is_empty() {
+        error!("Workload file contained no workloads");
+        return Err("Workload file contained no workloads".into());
+    }
+
+    let mut results = Vec::with_capacity(workloads.len());
+    for workload in &workloads {
+        let result = run_workload(workload, args.dry_run).await?;
+        if let Some(history) = &args.history {
+            append_history(history, &result)?;
+        }
+        results.push(result);
+    }
+
+    // Machine-readable summary to stdout (logs go to stderr).
+    println!("{}", serde_json::to_string_pretty(&results)?);
+
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use chrono::{Datelike, Duration, Utc};
+use clap::Parser;
+use fake::faker::internet::en::FreeEmail;
+use fake::faker::name::en::{FirstName, LastName};
+use fake::Fake;
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{error, info};
+use medapp_generators::measures::{MeasureDefinition, MeasureSummary, PopulationSpec};
+use medapp_generators::{connect_to_mongodb, hash_password, setup_logger, INITIAL_SCHEMA_VERSION};
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use std::error::Error;
+
+#[derive(Parser, Debug)]
+#[clap(
+    author,
+    version,
+    about = "Generate cross-referenced data satisfying a clinical quality measure"
+)]
+struct Args {
+    /// Path to the measure definition JSON file.
+    #[clap(long)]
+    measure: String,
+
+    /// Size of the initial patient population.
+    #[clap(short, long, default_value_t = 100)]
+    number: usize,
+
+    /// Fraction of denominator patients that should land in the numerator.
+    #[clap(long, default_value_t = 0.8)]
+    satisfy_rate: f64,
+
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+// A drug that is deliberately different from the numerator medication, used to
+// keep denominator-only patients out of the numerator.
+const FILLER_MEDICATION: &str = "Paracetamol";
+
+// Load doctor IDs so prescriptions/reports reference real practitioners.
+async fn cache_doctor_ids(db: &mongodb::Database) -> Result<Vec<ObjectId>, Box<dyn Error>> {
+    let collection = db.collection::<Document>("doctors");
+    let mut cursor = collection.find(None, None).await?;
+    let mut ids = Vec::new();
+    while let Some(result) = cursor.next().await {
+        ids.push(result?.get_object_id("_id").unwrap());
+    }
+    if ids.is_empty() {
+        return Err("No doctors found in the database. Please generate them first.".into());
+    }
+    Ok(ids)
+}
+
+// Build a minimal patient document whose age falls inside the measure's
+// initial-population bounds, so an engine that filters by age keeps the whole
+// generated population and the emitted bucket counts stay known-correct.
+fn patient_doc(population: &PopulationSpec, rng: &mut impl Rng) -> Document {
+    let name = format!("{} {}", FirstName().fake::<String>(), LastName().fake::<String>());
+    let min_age = population.min_age.unwrap_or(0);
+    let max_age = population.max_age.unwrap_or(min_age.max(90)).max(min_age);
+    // Anchor the birth date inside the year that ends on the patient's
+    // `age`-th birthday so the integer age lands in `[min_age, max_age]`.
+    let age = rng.gen_range(min_age..=max_age);
+    let today = Utc::now().date_naive();
+    let birth_year = today.year() - age as i32;
+    let latest = today
+        .with_year(birth_year)
+        .or_else(|| today.pred_opt()?.with_year(birth_year))
+        .expect("birth date out of range");
+    let birth_date = latest - Duration::days(rng.gen_range(0..365));
+    doc! {
+        "name": name,
+        "email": FreeEmail().fake::<String>(),
+        "date_of_birth": birth_date.to_string(),
+        "password_hash": hash_password("password"),
+        "schema_version": INITIAL_SCHEMA_VERSION,
+    }
+}
+
+// Build a prescription linking a patient to a diagnosis and one medication,
+// dated `date`.
+fn ordonnance_doc(
+    patient_id: ObjectId,
+    doctor_id: ObjectId,
+    diagnosis: &str,
+    medication: &str,
+    date: String,
+) -> Document {
+    doc! {
+        "patient_id": patient_id,
+        "doctor_id": doctor_id,
+        "diagnosis": diagnosis,
+        "medications": [ doc! { "name": medication, "dosage": "500 mg", "frequency": "2 times per day" } ],
+        "date": date,
+        "schema_version": INITIAL_SCHEMA_VERSION,
+    }
+}
+
+// Build a radiology report linking a patient, dated within the measure window.
+fn report_doc(
+    patient_id: ObjectId,
+    doctor_id: ObjectId,
+    exam_type: &str,
+    date: String,
+) -> Document {
+    doc! {
+        "patient_id": patient_id,
+        "doctor_id": doctor_id,
+        "exam_type": exam_type,
+        "body_part": "Thorax",
+        "exam_date": date,
+        "conclusion": "Normal",
+        "schema_version": INITIAL_SCHEMA_VERSION,
+    }
+}
+
+async fn generate_for_measure(
+    measure: &MeasureDefinition,
+    count: usize,
+    satisfy_rate: f64,
+) -> Result<MeasureSummary, Box<dyn Error>> {
+    let (db, _client) = connect_to_mongodb().await?;
+    let doctor_ids = cache_doctor_ids(&db).await?;
+
+    let patients = db.collection::<Document>("patients");
+    let ordonnances = db.collection::<Document>("ordonnances");
+    let reports = db.collection::<Document>("reports");
+
+    let numerator_target = (count as f64 * satisfy_rate).round() as usize;
+    info!(
+        "Generating {} patients for measure '{}' ({} expected in numerator)",
+        count, measure.id, numerator_target
+    );
+
+    let denominator = &measure.denominator;
+    let numerator = &measure.numerator;
+    let diagnosis = denominator
+        .diagnosis
+        .clone()
+        .unwrap_or_else(|| "Unspecified".to_string());
+    let medication = numerator
+        .medication
+        .clone()
+        .unwrap_or_else(|| "Metformin".to_string());
+    let window = numerator.within_days.unwrap_or(90);
+
+    let pb = ProgressBar::new(count as u64);
+    pb.set_style(ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} patients generated ({eta})")
+        .unwrap()
+        .progress_chars("#>-"));
+
+    let mut summary = MeasureSummary::default();
+    for i in 0..count {
+        let mut rng = thread_rng();
+        let doctor_id = *doctor_ids.choose(&mut rng).unwrap();
+
+        let patient_id = patients
+            .insert_one(patient_doc(&measure.initial_population, &mut rng), None)
+            .await?
+            .inserted_id
+            .as_object_id()
+            .ok_or("patient insert did not return an ObjectId")?;
+        summary.initial_population += 1;
+
+        // Index event for this patient, somewhere in the last year.
+        let index_date = Utc::now() - Duration::days(rng.gen_range(0..365));
+
+        let in_numerator = i < numerator_target;
+
+        // Every patient meets the denominator (carries the diagnosis).
+        // Numerator patients get the required medication within the window and,
+        // if demanded, a linked report. Denominator-only patients get a filler
+        // medication dated outside the window and no report.
+        let (drug, prescription_date) = if in_numerator {
+            (
+                medication.as_str(),
+                (index_date + Duration::days(rng.gen_range(0..=window))).to_rfc3339(),
+            )
+        } else {
+            (
+                FILLER_MEDICATION,
+                (index_date + Duration::days(window + rng.gen_range(1..30))).to_rfc3339(),
+            )
+        };
+
+        ordonnances
+            .insert_one(
+                ordonnance_doc(patient_id, doctor_id, &diagnosis, drug, prescription_date),
+                None,
+            )
+            .await?;
+        summary.denominator += 1;
+
+        if in_numerator {
+            if numerator.requires_report {
+                let exam_type = numerator
+                    .report_exam_type
+                    .clone()
+                    .unwrap_or_else(|| "Radiographie".to_string());
+                let report_date =
+                    (index_date + Duration::days(rng.gen_range(0..=window))).to_rfc3339();
+                reports
+                    .insert_one(report_doc(patient_id, doctor_id, &exam_type, report_date), None)
+                    .await?;
+            }
+            summary.numerator += 1;
+        }
+
+        pb.inc(1);
+    }
+
+    pb.finish_with_message("Measure dataset generated");
+    Ok(summary)
+}
+
+fn report_summary(measure: &MeasureDefinition, summary: &MeasureSummary) {
+    info!("Measure '{}' — {}", measure.id, measure.title);
+    info!("  initial population : {}", summary.initial_population);
+    info!("  denominator        : {}", summary.denominator);
+    info!("  numerator          : {}", summary.numerator);
+    info!("  denominator only   : {}", summary.denominator_only());
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    setup_logger(args.verbose);
+
+    if args.number == 0 {
+        error!("Population size must be greater than 0");
+        return Err("Population size must be greater than 0".into());
+    }
+    if !(0.0..=1.0).contains(&args.satisfy_rate) {
+        error!("--satisfy-rate must be between 0.0 and 1.0");
+        return Err("--satisfy-rate must be between 0.0 and 1.0".into());
+    }
+
+    let measure = MeasureDefinition::from_file(&args.measure)?;
+    let summary = generate_for_measure(&measure, args.number, args.satisfy_rate).await?;
+    report_summary(&measure, &summary);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use clap::Parser;
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{info, warn};
+use medapp_generators::migrations::{self, Migration};
+use medapp_generators::{connect_to_mongodb, setup_logger};
+use mongodb::bson::{doc, Document};
+use std::error::Error;
+
+#[derive(Parser, Debug)]
+#[clap(
+    author,
+    version,
+    about = "Rewrite generated documents up to the current schema_version (document-version migrations; see `medapp-gen db-migrate` for operational DB migrations)"
+)]
+struct Args {
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+// Documents below a migration's target version (missing `schema_version`
+// counts as version 0).
+fn pending_filter(version: i32) -> Document {
+    doc! {
+        "$or": [
+            { "schema_version": { "$lt": version } },
+            { "schema_version": { "$exists": false } },
+        ]
+    }
+}
+
+async fn run_migration(
+    db: &mongodb::Database,
+    migration: &Migration,
+) -> Result<u64, Box<dyn Error>> {
+    let source = db.collection::<Document>(migration.collection);
+    let filter = pending_filter(migration.version);
+
+    let total = source.count_documents(filter.clone(), None).await?;
+    if total == 0 {
+        return Ok(0);
+    }
+
+    info!(
+        "Migration {:03}: {} ({} document(s))",
+        migration.version, migration.description, total
+    );
+
+    let pb = ProgressBar::new(total);
+    pb.set_style(ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} migrated ({eta})")
+        .unwrap()
+        .progress_chars("#>-"));
+
+    let mut migrated = 0u64;
+    let mut cursor = source.find(filter, None).await?;
+    while let Some(result) = cursor.next().await {
+        let document = result?;
+        let id = document.get("_id").cloned();
+
+        let mut upgraded = (migration.up)(document);
+        upgraded.insert("schema_version", migration.version);
+
+        match migration.target_collection {
+            // Move into the target collection, then drop the source document so
+            // a re-run finds nothing (idempotent).
+            Some(target) if target != migration.collection => {
+                let target = db.collection::<Document>(target);
+                target.insert_one(&upgraded, None).await?;
+                if let Some(id) = id {
+                    source.delete_one(doc! { "_id": id }, None).await?;
+                }
+            }
+            // Rewrite in place.
+            _ => {
+                if let Some(id) = id {
+                    source
+                        .replace_one(doc! { "_id": id }, &upgraded, None)
+                        .await?;
+                }
+            }
+        }
+
+        migrated += 1;
+        pb.inc(1);
+    }
+
+    pb.finish_and_clear();
+    Ok(migrated)
+}
+
+async fn migrate() -> Result<(), Box<dyn Error>> {
+    let (db, _client) = connect_to_mongodb().await?;
+
+    info!(
+        "Running migrations up to schema version {}",
+        migrations::CURRENT_SCHEMA_VERSION
+    );
+
+    let mut total = 0u64;
+    for migration in migrations::all() {
+        let count = run_migration(&db, &migration).await?;
+        info!("Migration {:03}: migrated {} document(s)", migration.version, count);
+        total += count;
+    }
+
+    if total == 0 {
+        warn!("No documents needed migration; dataset already at the current version");
+    } else {
+        info!("Migrations complete: {} document(s) upgraded", total);
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    setup_logger(args.verbose);
+
+    migrate().await?;
+
+    Ok(())
+}
@@ -1,13 +1,21 @@
 use chrono::{Duration, Utc};
 use clap::Parser;
 use fake::faker::lorem::en::Paragraph;
-use fake::{Fake, Faker};
+use fake::Fake;
 use futures::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{debug, error, info};
-use medapp_generators::{connect_to_mongodb, setup_logger};
+use medapp_generators::fhir::{
+    self, Bundle, BundleEntry, CodeableConcept, Dosage, MedicationRequest, Reference,
+};
+use medapp_generators::indexer::{Indexable, IndexerConfig, SearchIndexer};
+use medapp_generators::{
+    connect_to_mongodb, insert_batches, parse_write_concern, setup_logger, GenCtx,
+    InsertRunOptions, OutputFormat, INITIAL_SCHEMA_VERSION,
+};
+use serde_json::json;
 use mongodb::bson::{doc, oid::ObjectId, Document};
-use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 
@@ -35,11 +43,128 @@ struct Ordonnance {
 #[derive(Parser, Debug)]
 #[clap(author, version, about = "Generate random prescriptions")]
 struct Args {
-    #[clap(short, long, default_value_t = 20)]
+    #[clap(short, long, alias = "count", default_value_t = 20)]
     number: usize,
 
+    /// Fraction (0.0..=1.0) of reference fields to deliberately point at
+    /// non-existent documents, for negative testing of referential integrity.
+    #[clap(long, default_value_t = 0.0)]
+    orphan_rate: f64,
+
+    /// Seed the random generator for reproducible runs.
+    #[clap(long)]
+    seed: Option<u64>,
+
     #[clap(short, long)]
     verbose: bool,
+
+    /// Output format: `mongo` inserts BSON documents, `fhir` emits a FHIR R4B
+    /// transaction Bundle of `MedicationRequest` resources (one per medication).
+    #[clap(long, value_enum, default_value_t = OutputFormat::Mongo)]
+    format: OutputFormat,
+
+    /// When generating FHIR output, POST the Bundle to this base URL instead of
+    /// writing it to disk.
+    #[clap(long)]
+    fhir_server: Option<String>,
+
+    /// Path to write the FHIR Bundle JSON to (defaults to `ordonnances.fhir.json`).
+    #[clap(long)]
+    fhir_output: Option<String>,
+
+    /// Wrap the whole run in a single multi-document transaction, rolling back
+    /// everything on any failure.
+    #[clap(long)]
+    all_or_nothing: bool,
+
+    /// Write concern required to acknowledge each batch, e.g. `majority` or `2`.
+    #[clap(long)]
+    write_concern: Option<String>,
+
+    /// Mirror the generated prescriptions into a full-text search index.
+    #[clap(long)]
+    index: bool,
+
+    /// Base URL of the search server (defaults to `http://localhost:7700`).
+    #[clap(long)]
+    index_url: Option<String>,
+
+    /// Override the index name (defaults to the collection name).
+    #[clap(long)]
+    index_name: Option<String>,
+}
+
+impl Indexable for Ordonnance {
+    fn index_name() -> &'static str {
+        "ordonnances"
+    }
+
+    fn searchable_attributes() -> Vec<&'static str> {
+        vec!["doctor_name", "diagnosis", "medications.name"]
+    }
+
+    fn filterable_attributes() -> Vec<&'static str> {
+        vec!["diagnosis"]
+    }
+
+    fn primary_key(&self) -> String {
+        // No natural single-field key, so combine the patient, date, and a
+        // sanitized fingerprint of the prescribed drugs.
+        let drugs: String = self
+            .medications
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}_{}_{}", self.patient_id.to_hex(), self.date, drugs)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn to_search_document(&self) -> serde_json::Value {
+        json!({
+            "id": self.primary_key(),
+            "doctor_name": self.doctor_name,
+            "diagnosis": self.diagnosis,
+            "medications": self.medications
+                .iter()
+                .map(|m| json!({ "name": m.name }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Ordonnance {
+    /// Map this prescription onto one FHIR `MedicationRequest` per medication.
+    ///
+    /// Each request references the same `Patient/<id>` subject and carries the
+    /// prescription's diagnosis as a `reasonCode`, its date as `authoredOn`,
+    /// and the medication's dosage/frequency/duration as a `dosageInstruction`.
+    fn to_medication_requests(&self) -> Vec<MedicationRequest> {
+        let subject = Reference::new("Patient", &self.patient_id.to_hex());
+        let reason = vec![CodeableConcept::text(self.diagnosis.clone())];
+
+        self.medications
+            .iter()
+            .map(|med| {
+                let mut text = format!("{} {} {}", med.name, med.dosage, med.frequency);
+                if let Some(duration) = &med.duration {
+                    text.push_str(&format!(" for {}", duration));
+                }
+                MedicationRequest {
+                    resource_type: "MedicationRequest".to_string(),
+                    status: "active".to_string(),
+                    intent: "order".to_string(),
+                    medication_codeable_concept: CodeableConcept::text(med.name.clone()),
+                    subject: subject.clone(),
+                    reason_code: Some(reason.clone()),
+                    authored_on: self.date.clone(),
+                    dosage_instruction: vec![Dosage { text: Some(text) }],
+                }
+            })
+            .collect()
+    }
 }
 
 // Cache IDs from the database to avoid repeated queries
@@ -82,9 +207,7 @@ async fn cache_ids(
 }
 
 // Generate a random medication
-fn generate_medication() -> Medication {
-    let mut rng = thread_rng();
-
+fn generate_medication(rng: &mut impl Rng) -> Medication {
     let medications = vec![
         "Amoxicillin", "Ibuprofen", "Paracetamol", "Aspirin", 
         "Loratadine", "Omeprazole", "Metformin", "Lisinopril",
@@ -93,7 +216,7 @@ fn generate_medication() -> Medication {
     ];
 
     Medication {
-        name: medications.choose(&mut rng).unwrap().to_string(),
+        name: medications.choose(rng).unwrap().to_string(),
         dosage: format!("{} mg", rng.gen_range(100..1001)),
         frequency: format!("{} times per day", rng.gen_range(1..4)),
         duration: Some(format!("{} days", rng.gen_range(3..15))),
@@ -101,10 +224,15 @@ fn generate_medication() -> Medication {
 }
 
 // Generate a random prescription using cached IDs
-fn generate_ordonnance(medecin_ids: &[ObjectId], patient_ids: &[ObjectId]) -> Ordonnance {
-    let mut rng = thread_rng();
+fn generate_ordonnance(
+    medecin_ids: &[ObjectId],
+    patient_ids: &[ObjectId],
+    orphan_rate: f64,
+    ctx: &mut GenCtx,
+) -> Ordonnance {
+    let now = ctx.now;
+    let rng = &mut ctx.rng;
 
-    let now = Utc::now();
     let one_year_ago = now - Duration::days(365);
     let random_days = rng.gen_range(0..(now - one_year_ago).num_days());
     let date_creation = (one_year_ago + Duration::days(random_days)).to_rfc3339();
@@ -126,28 +254,28 @@ fn generate_ordonnance(medecin_ids: &[ObjectId], patient_ids: &[ObjectId]) -> Or
     let last_names = vec!["Smith", "Johnson", "Williams", "Brown", "Jones", "Miller", "Davis", "Garcia", "Rodriguez", "Wilson"];
     let doctor_titles = vec!["Dr.", "Dr.", "Prof.", "Dr.", "Dr."];
     
-    let patient_first_name = first_names.choose(&mut rng).unwrap();
-    let patient_last_name = last_names.choose(&mut rng).unwrap();
+    let patient_first_name = first_names.choose(rng).unwrap();
+    let patient_last_name = last_names.choose(rng).unwrap();
     let patient_name = format!("{} {}", patient_first_name, patient_last_name);
-    
-    let doctor_first_name = first_names.choose(&mut rng).unwrap();
-    let doctor_last_name = last_names.choose(&mut rng).unwrap();
-    let doctor_title = doctor_titles.choose(&mut rng).unwrap();
+
+    let doctor_first_name = first_names.choose(rng).unwrap();
+    let doctor_last_name = last_names.choose(rng).unwrap();
+    let doctor_title = doctor_titles.choose(rng).unwrap();
     let doctor_name = format!("{} {} {}", doctor_title, doctor_first_name, doctor_last_name);
 
     let num_medications = rng.gen_range(1..4);
     let medications = (0..num_medications)
-        .map(|_| generate_medication())
+        .map(|_| generate_medication(rng))
         .collect();
 
     Ordonnance {
-        doctor_id: medecin_ids.choose(&mut rng).unwrap().clone(),
-        patient_id: patient_ids.choose(&mut rng).unwrap().clone(),
+        doctor_id: medapp_generators::pick_reference(medecin_ids, orphan_rate, rng),
+        patient_id: medapp_generators::pick_reference(patient_ids, orphan_rate, rng),
         patient_name,
         doctor_name,
         medications,
-        instructions: Paragraph(1..2).fake(),
-        diagnosis: common_diagnoses.choose(&mut rng).unwrap().to_string(),
+        instructions: Paragraph(1..2).fake_with_rng(rng),
+        diagnosis: common_diagnoses.choose(rng).unwrap().to_string(),
         date: date_creation,
         signature: if rng.gen_bool(0.7) {
             Some("data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAABg".to_string())
@@ -157,12 +285,69 @@ fn generate_ordonnance(medecin_ids: &[ObjectId], patient_ids: &[ObjectId]) -> Or
     }
 }
 
-async fn generate_ordonnances(count: usize) -> Result<(), Box<dyn Error>> {
+// Generate prescriptions as a FHIR transaction Bundle of MedicationRequest
+// resources, writing it to disk or POSTing it to a configured FHIR server.
+async fn generate_ordonnances_fhir(
+    count: usize,
+    orphan_rate: f64,
+    ctx: &mut GenCtx,
+    server: Option<String>,
+    output: Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let (db, _client) = connect_to_mongodb().await?;
+
+    // Cache IDs so references point at real patients/doctors.
+    let (medecin_ids, patient_ids) = cache_ids(&db).await?;
+
+    info!("Starting generation of {} prescriptions (FHIR)", count);
+
+    let pb = ProgressBar::new(count as u64);
+    pb.set_style(ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} prescriptions generated ({eta})")
+        .unwrap()
+        .progress_chars("#>-"));
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let ordonnance = generate_ordonnance(&medecin_ids, &patient_ids, orphan_rate, ctx);
+        for request in ordonnance.to_medication_requests() {
+            let resource = serde_json::to_value(request)?;
+            entries.push(BundleEntry::create(resource, "MedicationRequest"));
+        }
+        pb.inc(1);
+    }
+
+    let bundle = Bundle::transaction(entries);
+
+    match server {
+        Some(url) => {
+            info!("POSTing MedicationRequest Bundle to {}", url);
+            fhir::post_bundle(&url, &bundle).await?;
+        }
+        None => {
+            let path = output.unwrap_or_else(|| "ordonnances.fhir.json".to_string());
+            bundle.write_json(&path)?;
+            info!("Wrote FHIR Bundle to {}", path);
+        }
+    }
+
+    pb.finish_with_message("All prescriptions generated successfully");
+    Ok(())
+}
+
+async fn generate_ordonnances(
+    count: usize,
+    orphan_rate: f64,
+    ctx: &mut GenCtx,
+    opts: InsertRunOptions,
+    indexer: Option<SearchIndexer>,
+) -> Result<(), Box<dyn Error>> {
     let (db, client) = connect_to_mongodb().await?;
     let collection = db.collection::<Document>("ordonnances");
 
     // Cache IDs for better performance
     let (medecin_ids, patient_ids) = cache_ids(&db).await?;
+    let mut generated: Vec<Ordonnance> = Vec::new();
 
     info!("Starting generation of {} prescriptions", count);
 
@@ -176,19 +361,20 @@ async fn generate_ordonnances(count: usize) -> Result<(), Box<dyn Error>> {
     let batch_size = 100;
     let num_batches = (count + batch_size - 1) / batch_size;
 
+    let mut batches = Vec::with_capacity(num_batches);
     for batch_idx in 0..num_batches {
         let mut batch = Vec::with_capacity(batch_size);
         let current_batch_size = std::cmp::min(batch_size, count - batch_idx * batch_size);
 
         for _ in 0..current_batch_size {
-            let ordonnance = generate_ordonnance(&medecin_ids, &patient_ids);
+            let ordonnance = generate_ordonnance(&medecin_ids, &patient_ids, orphan_rate, ctx);
 
             batch.push(doc! {
                 "doctor_id": ordonnance.doctor_id,
                 "patient_id": ordonnance.patient_id,
-                "patient_name": ordonnance.patient_name,
-                "doctor_name": ordonnance.doctor_name,
-                "date": ordonnance.date,
+                "patient_name": &ordonnance.patient_name,
+                "doctor_name": &ordonnance.doctor_name,
+                "date": &ordonnance.date,
                 "medications": ordonnance.medications
                     .iter()
                     .map(|med| doc! {
@@ -198,33 +384,30 @@ async fn generate_ordonnances(count: usize) -> Result<(), Box<dyn Error>> {
                         "duration": &med.duration,
                     })
                     .collect::<Vec<Document>>(),
-                "instructions": ordonnance.instructions,
-                "diagnosis": ordonnance.diagnosis,
-                "signature": ordonnance.signature,
+                "instructions": &ordonnance.instructions,
+                "diagnosis": &ordonnance.diagnosis,
+                "signature": &ordonnance.signature,
+                "schema_version": INITIAL_SCHEMA_VERSION,
             });
-        }
 
-        // Insert batch
-        match collection.insert_many(batch, None).await {
-            Ok(result) => {
-                debug!(
-                    "Inserted batch {}/{} with {} prescriptions",
-                    batch_idx + 1,
-                    num_batches,
-                    result.inserted_ids.len()
-                );
-                pb.inc(current_batch_size as u64);
-            }
-            Err(e) => {
-                error!("Failed to insert batch: {}", e);
-                return Err(Box::new(e));
+            if indexer.is_some() {
+                generated.push(ordonnance);
             }
         }
+
+        debug!("Prepared batch {}/{}", batch_idx + 1, num_batches);
+        batches.push(batch);
     }
 
+    insert_batches(&client, &collection, batches, &opts, &pb).await?;
+
     pb.finish_with_message("All prescriptions generated successfully");
     info!("Successfully added {} prescriptions to the database", count);
 
+    if let Some(indexer) = indexer {
+        indexer.index_documents(&generated).await?;
+    }
+
     Ok(())
 }
 
@@ -238,7 +421,49 @@ async fn main() -> Result<(), Box<dyn Error>> {
         return Err("Number of prescriptions must be greater than 0".into());
     }
 
-    generate_ordonnances(args.number).await?;
+    if !(0.0..=1.0).contains(&args.orphan_rate) {
+        error!("Orphan rate must be between 0.0 and 1.0");
+        return Err("Orphan rate must be between 0.0 and 1.0".into());
+    }
+
+    let write_concern = match args.write_concern {
+        Some(ref s) => Some(parse_write_concern(s)?),
+        None => None,
+    };
+    let opts = InsertRunOptions {
+        all_or_nothing: args.all_or_nothing,
+        write_concern,
+        ..Default::default()
+    };
+
+    let indexer = args.index.then(|| {
+        SearchIndexer::new(&IndexerConfig {
+            url: args
+                .index_url
+                .clone()
+                .unwrap_or_else(|| "http://localhost:7700".to_string()),
+            index_name: args.index_name.clone(),
+            api_key: None,
+        })
+    });
+
+    let mut ctx = GenCtx::new(args.seed);
+
+    match args.format {
+        OutputFormat::Mongo => {
+            generate_ordonnances(args.number, args.orphan_rate, &mut ctx, opts, indexer).await?
+        }
+        OutputFormat::Fhir => {
+            generate_ordonnances_fhir(
+                args.number,
+                args.orphan_rate,
+                &mut ctx,
+                args.fhir_server,
+                args.fhir_output,
+            )
+            .await?
+        }
+    }
 
     Ok(())
 }
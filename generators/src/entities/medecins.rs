@@ -0,0 +1,114 @@
+use crate::fhir::{HumanName, Practitioner};
+use crate::indexer::Indexable;
+use crate::{hash_password_seeded, GenCtx};
+use fake::faker::address::en::StreetName;
+use fake::faker::internet::en::FreeEmail;
+use fake::faker::name::en::{FirstName, LastName};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Doctor {
+    pub name: String,
+    pub email: String,
+    pub specialty: String,
+    pub phone_number: String,
+    pub address: String,
+    pub password_hash: String,
+    pub is_verified: bool,
+    pub profile_image: Option<String>,
+}
+
+impl Indexable for Doctor {
+    fn index_name() -> &'static str {
+        "doctors"
+    }
+
+    fn searchable_attributes() -> Vec<&'static str> {
+        vec!["name", "specialty"]
+    }
+
+    fn filterable_attributes() -> Vec<&'static str> {
+        vec!["specialty", "is_verified"]
+    }
+
+    fn primary_key(&self) -> String {
+        // Index primary keys must be alphanumeric / `-` / `_`, so sanitize the
+        // email we use as a natural key.
+        self.email
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn to_search_document(&self) -> serde_json::Value {
+        json!({
+            "id": self.primary_key(),
+            "name": self.name,
+            "specialty": self.specialty,
+            "is_verified": self.is_verified,
+        })
+    }
+}
+
+impl Doctor {
+    /// Map this doctor onto a FHIR `Practitioner` resource.
+    pub fn to_practitioner(&self) -> Practitioner {
+        let mut parts = self.name.splitn(2, ' ');
+        let given = parts.next().unwrap_or_default().to_string();
+        let family = parts.next().map(|s| s.to_string());
+
+        Practitioner {
+            resource_type: "Practitioner".to_string(),
+            identifier: Vec::new(),
+            name: vec![HumanName {
+                family,
+                given: if given.is_empty() { Vec::new() } else { vec![given] },
+            }],
+            qualification: Vec::new(),
+        }
+    }
+}
+
+pub fn speciality_list() -> Vec<&'static str> {
+    vec![
+        "Cardiology",
+        "Dermatology",
+        "Neurology",
+        "Pediatrics",
+        "Radiology",
+        "Surgery",
+        "General Medicine",
+        "Ophthalmology",
+        "Gynecology",
+        "Orthopedics",
+        "Psychiatry",
+        "Urology",
+    ]
+}
+
+/// Generate a single random doctor, drawing all randomness from `ctx`.
+pub fn generate_doctor(specialities: &[&str], ctx: &mut GenCtx) -> Doctor {
+    let rng = &mut ctx.rng;
+
+    let name = format!(
+        "{} {}",
+        FirstName().fake_with_rng::<String, _>(rng),
+        LastName().fake_with_rng::<String, _>(rng)
+    );
+    let email = FreeEmail().fake_with_rng::<String, _>(rng);
+
+    Doctor {
+        name,
+        email,
+        specialty: specialities.choose(rng).unwrap().to_string(),
+        phone_number: PhoneNumber().fake_with_rng(rng),
+        address: StreetName().fake_with_rng(rng),
+        password_hash: hash_password_seeded("password", rng),
+        is_verified: rng.gen_bool(0.5),
+        profile_image: None,
+    }
+}
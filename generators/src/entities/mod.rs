@@ -0,0 +1,11 @@
+//! Per-entity document models and generators.
+//!
+//! Each submodule owns one collection's struct, its random generator, and any
+//! FHIR / search-index mappings. The unified `medapp-gen` CLI wires these into
+//! [`crate::generate_in_batches`]; keeping them here means adding a new entity
+//! type is a self-contained module rather than a whole new binary.
+
+pub mod medecins;
+pub mod patients;
+pub mod radiologues;
+pub mod reports;
@@ -0,0 +1,122 @@
+use crate::fhir::{self, Address, ContactPoint, HumanName, Identifier};
+use crate::{hash_password_seeded, GenCtx};
+use chrono::Duration;
+use fake::faker::address::en::StreetName;
+use fake::faker::internet::en::FreeEmail;
+use fake::faker::lorem::en::{Sentence, Word};
+use fake::faker::name::en::{FirstName, LastName};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Patient {
+    pub name: String,
+    pub email: String,
+    pub phoneNumber: String,
+    pub address: Option<String>,
+    pub gender: String,
+    pub date_of_birth: String,
+    pub blood_type: String,
+    pub social_security_number: String,
+    pub medical_history: Vec<String>,
+    pub allergies: Vec<String>,
+    pub registration_date: String,
+    pub password_hash: String,
+}
+
+impl Patient {
+    /// Map this patient onto a FHIR `Patient` resource.
+    pub fn to_fhir_patient(&self) -> fhir::Patient {
+        let mut parts = self.name.splitn(2, ' ');
+        let given = parts.next().unwrap_or_default().to_string();
+        let family = parts.next().map(|s| s.to_string());
+
+        let mut telecom = vec![ContactPoint::phone(self.phoneNumber.clone())];
+        telecom.push(ContactPoint::email(self.email.clone()));
+
+        fhir::Patient {
+            resource_type: "Patient".to_string(),
+            identifier: vec![Identifier {
+                system: Some("http://hl7.org/fhir/sid/fr-ssn".to_string()),
+                value: self.social_security_number.clone(),
+            }],
+            name: vec![HumanName {
+                family,
+                given: if given.is_empty() { Vec::new() } else { vec![given] },
+            }],
+            telecom,
+            gender: Some(self.gender.clone()),
+            birth_date: Some(self.date_of_birth.clone()),
+            address: self
+                .address
+                .clone()
+                .map(|text| vec![Address { text }])
+                .unwrap_or_default(),
+        }
+    }
+}
+
+pub fn blood_type_list() -> Vec<&'static str> {
+    vec!["A+", "A-", "B+", "B-", "AB+", "AB-", "O+", "O-"]
+}
+
+/// Generate a single random patient, drawing all randomness from `ctx`.
+pub fn generate_patient(blood_types: &[&str], ctx: &mut GenCtx) -> Patient {
+    let now = ctx.now;
+    let rng = &mut ctx.rng;
+
+    let two_years_ago = now - Duration::days(365 * 2);
+    let random_days = rng.gen_range(0..(now - two_years_ago).num_days());
+    let date_inscription = (two_years_ago + Duration::days(random_days)).to_rfc3339();
+
+    // Generate a birth date between 1 and 90 years ago
+    let years_ago = rng.gen_range(1..91);
+    let birth_date = (now - Duration::days(365 * years_ago)).date_naive();
+
+    let nom = LastName().fake_with_rng::<String, _>(rng);
+    let prenom = FirstName().fake_with_rng::<String, _>(rng);
+    let email = FreeEmail().fake_with_rng::<String, _>(rng);
+
+    let numero_securite_sociale = (0..15)
+        .map(|_| rng.gen_range(0..10).to_string())
+        .collect::<String>();
+
+    let antecedents: Vec<String> = if rng.gen_bool(0.3) {
+        (0..rng.gen_range(1..4))
+            .map(|_| Sentence(3..10).fake_with_rng(rng))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let allergies: Vec<String> = if rng.gen_bool(0.5) {
+        (0..rng.gen_range(1..4))
+            .map(|_| Word().fake_with_rng(rng))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let gender = ["male", "female", "other"]
+        .choose(rng)
+        .unwrap()
+        .to_string();
+
+    Patient {
+        name: format!("{} {}", prenom, nom),
+        email,
+        phoneNumber: PhoneNumber().fake_with_rng(rng),
+        address: Some(StreetName().fake_with_rng(rng)),
+        gender,
+        date_of_birth: birth_date.to_string(),
+        blood_type: blood_types.choose(rng).unwrap().to_string(),
+        social_security_number: numero_securite_sociale,
+        medical_history: antecedents,
+        allergies,
+        registration_date: date_inscription,
+        password_hash: hash_password_seeded("password", rng),
+    }
+}
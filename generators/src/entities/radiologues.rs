@@ -0,0 +1,108 @@
+use crate::fhir::{CodeableConcept, Coding, HumanName, Identifier, Practitioner, Qualification};
+use crate::{hash_password_seeded, GenCtx};
+use chrono::Duration;
+use fake::faker::address::en::StreetName;
+use fake::faker::internet::en::FreeEmail;
+use fake::faker::name::en::{FirstName, LastName};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(non_snake_case)]
+pub struct Radiologue {
+    pub nom: String,
+    pub prenom: String,
+    pub email: String,
+    pub telephone: String,
+    pub adresse: String,
+    pub specialiteRadiologie: String,
+    pub equipements: Vec<String>,
+    pub dateInscription: String,
+    pub numeroOrdre: String,
+    pub password: String,
+}
+
+impl Radiologue {
+    /// Map this radiologist onto a FHIR `Practitioner` resource, carrying the
+    /// `numeroOrdre` as an identifier and the radiology speciality as a
+    /// `qualification` coding.
+    pub fn to_practitioner(&self) -> Practitioner {
+        Practitioner {
+            resource_type: "Practitioner".to_string(),
+            identifier: vec![Identifier {
+                system: Some("urn:medapp:numeroOrdre".to_string()),
+                value: self.numeroOrdre.clone(),
+            }],
+            name: vec![HumanName {
+                family: Some(self.nom.clone()),
+                given: vec![self.prenom.clone()],
+            }],
+            qualification: vec![Qualification {
+                code: CodeableConcept {
+                    coding: vec![Coding {
+                        system: Some("urn:medapp:specialiteRadiologie".to_string()),
+                        code: Some(self.specialiteRadiologie.clone()),
+                        display: self.specialiteRadiologie.clone(),
+                    }],
+                    text: None,
+                },
+            }],
+        }
+    }
+}
+
+pub fn equipment_list() -> Vec<&'static str> {
+    vec!["IRM", "Scanner", "Échographie", "Radiographie", "Mammographie"]
+}
+
+pub fn radiology_type_list() -> Vec<&'static str> {
+    vec!["Général", "Neurologique", "Musculosquelettique", "Abdominale", "Thoracique"]
+}
+
+/// Generate a single random radiologist, drawing all randomness from `ctx`.
+pub fn generate_radiologue(
+    equipments: &[&str],
+    radiology_types: &[&str],
+    ctx: &mut GenCtx,
+) -> Radiologue {
+    let now = ctx.now;
+    let rng = &mut ctx.rng;
+
+    let five_years_ago = now - Duration::days(365 * 5);
+    let random_days = rng.gen_range(0..(now - five_years_ago).num_days());
+    let date_inscription = (five_years_ago + Duration::days(random_days)).to_rfc3339();
+
+    let nom = LastName().fake_with_rng::<String, _>(rng);
+    let prenom = FirstName().fake_with_rng::<String, _>(rng);
+    let email = FreeEmail().fake_with_rng::<String, _>(rng);
+
+    let numero_ordre = format!("RD{}", (0..6).map(|_| rng.gen_range(0..10).to_string()).collect::<String>());
+
+    // Randomly select 1 to 3 equipment items without duplicates
+    let num_equipments = rng.gen_range(1..=3);
+    let mut selected_equipments = Vec::new();
+    let mut available_equipments = equipments.to_vec();
+
+    for _ in 0..num_equipments {
+        if available_equipments.is_empty() {
+            break;
+        }
+        let idx = rng.gen_range(0..available_equipments.len());
+        selected_equipments.push(available_equipments.remove(idx).to_string());
+    }
+
+    Radiologue {
+        nom,
+        prenom,
+        email,
+        telephone: PhoneNumber().fake_with_rng(rng),
+        adresse: StreetName().fake_with_rng(rng),
+        specialiteRadiologie: radiology_types.choose(rng).unwrap().to_string(),
+        equipements: selected_equipments,
+        dateInscription: date_inscription,
+        numeroOrdre: numero_ordre,
+        password: hash_password_seeded("password", rng),
+    }
+}
@@ -0,0 +1,214 @@
+use crate::fhir::{CodeableConcept, DiagnosticReport, ImagingStudy, Reference};
+use crate::indexer::Indexable;
+use crate::GenCtx;
+use chrono::Duration;
+use futures::StreamExt;
+use log::info;
+use mongodb::bson::{oid::ObjectId, Document};
+use mongodb::Database;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub condition: String,
+    pub severity: String,
+    pub description: String,
+    pub confidence_score: f64,
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageQualityMetrics {
+    pub contrast: String,
+    pub sharpness: String,
+    pub exposure: String,
+    pub positioning: String,
+    pub noise_level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechnicalDetails {
+    pub quality_metrics: ImageQualityMetrics,
+    pub image_stats: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Analysis {
+    pub findings: Vec<Finding>,
+    pub technical_details: TechnicalDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub title: String,
+    pub content: String,
+    pub patient_id: ObjectId,
+    pub doctor_id: ObjectId,
+    pub radiologist_id: ObjectId,
+    pub exam_type: String,
+    pub body_part: String,
+    pub exam_date: String,
+    pub conclusion: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<Analysis>,
+    pub tags: Vec<String>,
+    pub image_path: String,
+}
+
+impl Indexable for Report {
+    fn index_name() -> &'static str {
+        "reports"
+    }
+
+    fn searchable_attributes() -> Vec<&'static str> {
+        vec!["conclusion", "body_part", "tags"]
+    }
+
+    fn filterable_attributes() -> Vec<&'static str> {
+        vec!["exam_type", "body_part"]
+    }
+
+    fn primary_key(&self) -> String {
+        // Reuse the UUID embedded in the generated image path as a stable key.
+        self.image_path
+            .rsplit('/')
+            .next()
+            .and_then(|f| f.strip_suffix(".jpg"))
+            .unwrap_or(&self.image_path)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn to_search_document(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.primary_key(),
+            "conclusion": self.conclusion,
+            "body_part": self.body_part,
+            "exam_type": self.exam_type,
+            "tags": self.tags,
+        })
+    }
+}
+
+impl Report {
+    /// Map this report onto a `DiagnosticReport`/`ImagingStudy` pair, linked via
+    /// a `urn:uuid:` reference so both can be created in one transaction.
+    ///
+    /// `study_url` is the study's `fullUrl`; the report's `imagingStudy`
+    /// reference points at it. Returns the two resources.
+    pub fn to_fhir_pair(&self, study_url: &str) -> (DiagnosticReport, ImagingStudy) {
+        let subject = Reference::new("Patient", &self.patient_id.to_hex());
+        let code = CodeableConcept::text(format!("{} - {}", self.exam_type, self.body_part));
+
+        let imaging_study = ImagingStudy {
+            resource_type: "ImagingStudy".to_string(),
+            status: "available".to_string(),
+            subject: subject.clone(),
+            procedure_code: vec![CodeableConcept::text(self.exam_type.clone())],
+            description: Some(self.body_part.clone()),
+        };
+
+        let diagnostic_report = DiagnosticReport {
+            resource_type: "DiagnosticReport".to_string(),
+            status: "final".to_string(),
+            code,
+            subject,
+            effective_date_time: Some(self.exam_date.clone()),
+            performer: vec![Reference::new("Practitioner", &self.radiologist_id.to_hex())],
+            conclusion: Some(self.conclusion.clone()),
+            imaging_study: vec![Reference {
+                reference: study_url.to_string(),
+            }],
+        };
+
+        (diagnostic_report, imaging_study)
+    }
+}
+
+/// Load the `_id`s of every patient, radiologist, and doctor once so that
+/// generated reports reference real, existing documents across the whole run.
+pub async fn cache_ids(
+    db: &Database,
+) -> Result<(Vec<ObjectId>, Vec<ObjectId>, Vec<ObjectId>), Box<dyn Error>> {
+    info!("Caching patient, radiologist, and doctor IDs");
+
+    let patients_collection = db.collection::<Document>("patients");
+    let radiologues_collection = db.collection::<Document>("radiologues");
+    let medecins_collection = db.collection::<Document>("doctors");
+
+    let mut patient_cursor = patients_collection.find(None, None).await?;
+    let mut patient_ids = Vec::new();
+    while let Some(result) = patient_cursor.next().await {
+        let document = result?;
+        patient_ids.push(document.get_object_id("_id").unwrap());
+    }
+
+    let mut radiologue_cursor = radiologues_collection.find(None, None).await?;
+    let mut radiologue_ids = Vec::new();
+    while let Some(result) = radiologue_cursor.next().await {
+        let document = result?;
+        radiologue_ids.push(document.get_object_id("_id").unwrap());
+    }
+
+    let mut medecin_cursor = medecins_collection.find(None, None).await?;
+    let mut medecin_ids = Vec::new();
+    while let Some(result) = medecin_cursor.next().await {
+        let document = result?;
+        medecin_ids.push(document.get_object_id("_id").unwrap());
+    }
+
+    if patient_ids.is_empty() || radiologue_ids.is_empty() || medecin_ids.is_empty() {
+        return Err("Missing required entities in the database. Please generate them first.".into());
+    }
+
+    info!(
+        "Cached {} patient IDs, {} radiologist IDs, and {} doctor IDs",
+        patient_ids.len(),
+        radiologue_ids.len(),
+        medecin_ids.len()
+    );
+
+    Ok((patient_ids, radiologue_ids, medecin_ids))
+}
+
+/// Generate a random report using cached IDs, drawing all randomness from `ctx`.
+pub fn generate_report(
+    patient_ids: &[ObjectId],
+    radiologue_ids: &[ObjectId],
+    medecin_ids: &[ObjectId],
+    orphan_rate: f64,
+    ctx: &mut GenCtx,
+) -> Report {
+    let now = ctx.now;
+    let rng = &mut ctx.rng;
+
+    let report_types = vec!["IRM", "Scanner", "Échographie", "Radiographie", "Mammographie"];
+    let body_parts = vec!["Tête", "Thorax", "Abdomen", "Membres inférieurs", "Membres supérieurs", "Colonne vertébrale", "Bassin"];
+    let findings = vec!["Normal", "Légère anomalie", "Anomalie significative", "Résultats préoccupants", "Résultats critiques"];
+
+    let six_months_ago = now - Duration::days(180);
+    let random_days = rng.gen_range(0..(now - six_months_ago).num_days());
+    let date_examen = (six_months_ago + Duration::days(random_days)).to_rfc3339();
+
+    let image_uuid = Uuid::from_bytes(rng.gen());
+
+    Report {
+        title: "Random Report Title".to_string(),
+        content: "Random Report Content".to_string(),
+        patient_id: crate::pick_reference(patient_ids, orphan_rate, rng),
+        radiologist_id: crate::pick_reference(radiologue_ids, orphan_rate, rng),
+        doctor_id: crate::pick_reference(medecin_ids, orphan_rate, rng),
+        exam_type: report_types.choose(rng).unwrap().to_string(),
+        body_part: body_parts.choose(rng).unwrap().to_string(),
+        exam_date: date_examen,
+        conclusion: findings.choose(rng).unwrap().to_string(),
+        analysis: None,
+        tags: vec!["tag1".to_string(), "tag2".to_string()],
+        image_path: format!("/images/reports/{}.jpg", image_uuid),
+    }
+}
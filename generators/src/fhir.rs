@@ -0,0 +1,382 @@
+//! FHIR R4B resource structs and helpers.
+//!
+//! The generators natively emit BSON documents shaped for this project's Mongo
+//! collections. This module provides a second, standards-compliant target: the
+//! same generated entities can be mapped onto FHIR R4B resources and shipped as
+//! a transaction [`Bundle`] that any FHIR-capable system can ingest.
+//!
+//! Only the fields the generators actually populate are modelled; everything
+//! else is left to the server's defaults. Resources are kept as plain
+//! `serde`-serializable structs and collected into a bundle as opaque
+//! `serde_json::Value`s so heterogeneous resource types can share one entry
+//! list.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use std::path::Path;
+
+/// A coded value, e.g. a diagnosis or a drug code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Coding {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub display: String,
+}
+
+/// A concept described by a coding and/or free text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeableConcept {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub coding: Vec<Coding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl CodeableConcept {
+    /// A concept carrying only free text, used when we have no coding system.
+    pub fn text(display: impl Into<String>) -> Self {
+        CodeableConcept {
+            coding: Vec::new(),
+            text: Some(display.into()),
+        }
+    }
+}
+
+/// A typed reference to another resource, e.g. `"Patient/<id>"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reference {
+    pub reference: String,
+}
+
+impl Reference {
+    /// Build a `"<ResourceType>/<id>"` reference.
+    pub fn new(resource_type: &str, id: &str) -> Self {
+        Reference {
+            reference: format!("{}/{}", resource_type, id),
+        }
+    }
+}
+
+/// A person's name (`HumanName`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanName {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub given: Vec<String>,
+}
+
+/// A business identifier carried on a resource (`Identifier`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Identifier {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    pub value: String,
+}
+
+/// A single medication dose instruction (`Dosage`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dosage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// A contact detail (`ContactPoint`), e.g. a phone number or email address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactPoint {
+    pub system: String,
+    pub value: String,
+}
+
+impl ContactPoint {
+    /// A `phone` contact point.
+    pub fn phone(value: impl Into<String>) -> Self {
+        ContactPoint {
+            system: "phone".to_string(),
+            value: value.into(),
+        }
+    }
+
+    /// An `email` contact point.
+    pub fn email(value: impl Into<String>) -> Self {
+        ContactPoint {
+            system: "email".to_string(),
+            value: value.into(),
+        }
+    }
+}
+
+/// A postal address (`Address`), modelled as free text here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Address {
+    pub text: String,
+}
+
+/// A `Practitioner.qualification` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Qualification {
+    pub code: CodeableConcept,
+}
+
+/// A FHIR `Practitioner`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Practitioner {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub identifier: Vec<Identifier>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub name: Vec<HumanName>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub qualification: Vec<Qualification>,
+}
+
+/// A FHIR `Patient`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Patient {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub identifier: Vec<Identifier>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub name: Vec<HumanName>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub telecom: Vec<ContactPoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "birthDate")]
+    pub birth_date: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub address: Vec<Address>,
+}
+
+/// A FHIR `MedicationRequest` (one per prescribed medication).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicationRequest {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    pub status: String,
+    pub intent: String,
+    #[serde(rename = "medicationCodeableConcept")]
+    pub medication_codeable_concept: CodeableConcept,
+    pub subject: Reference,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "reasonCode")]
+    pub reason_code: Option<Vec<CodeableConcept>>,
+    #[serde(rename = "authoredOn")]
+    pub authored_on: String,
+    #[serde(rename = "dosageInstruction", skip_serializing_if = "Vec::is_empty", default)]
+    pub dosage_instruction: Vec<Dosage>,
+}
+
+/// A FHIR `DiagnosticReport`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticReport {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    pub status: String,
+    pub code: CodeableConcept,
+    pub subject: Reference,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "effectiveDateTime")]
+    pub effective_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub performer: Vec<Reference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default, rename = "imagingStudy")]
+    pub imaging_study: Vec<Reference>,
+}
+
+/// A FHIR `ImagingStudy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagingStudy {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    pub status: String,
+    pub subject: Reference,
+    #[serde(skip_serializing_if = "Vec::is_empty", default, rename = "procedureCode")]
+    pub procedure_code: Vec<CodeableConcept>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The `request` slot of a transaction bundle entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleRequest {
+    pub method: String,
+    pub url: String,
+}
+
+impl BundleRequest {
+    /// A `POST <ResourceType>` create request.
+    pub fn post(resource_type: &str) -> Self {
+        BundleRequest {
+            method: "POST".to_string(),
+            url: resource_type.to_string(),
+        }
+    }
+}
+
+/// A single entry in a [`Bundle`], carrying one resource and its request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleEntry {
+    #[serde(skip_serializing_if = "Option::is_none", rename = "fullUrl")]
+    pub full_url: Option<String>,
+    pub resource: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<BundleRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<Value>,
+}
+
+impl BundleEntry {
+    /// Wrap a serialized resource as a `POST` transaction entry.
+    pub fn create(resource: Value, resource_type: &str) -> Self {
+        BundleEntry {
+            full_url: None,
+            resource,
+            request: Some(BundleRequest::post(resource_type)),
+            response: None,
+        }
+    }
+
+    /// A `POST` entry with a `urn:uuid:` `fullUrl`, so other entries in the
+    /// same transaction can reference it before the server assigns an id.
+    pub fn create_with_url(resource: Value, resource_type: &str, full_url: String) -> Self {
+        BundleEntry {
+            full_url: Some(full_url),
+            ..BundleEntry::create(resource, resource_type)
+        }
+    }
+}
+
+/// A FHIR `Bundle`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    #[serde(rename = "resourceType")]
+    pub resource_type: String,
+    #[serde(rename = "type")]
+    pub bundle_type: String,
+    #[serde(default)]
+    pub entry: Vec<BundleEntry>,
+}
+
+impl Bundle {
+    /// Build a `type: "transaction"` bundle from the given entries.
+    pub fn transaction(entries: Vec<BundleEntry>) -> Self {
+        Bundle {
+            resource_type: "Bundle".to_string(),
+            bundle_type: "transaction".to_string(),
+            entry: entries,
+        }
+    }
+
+    /// Serialize the bundle to pretty JSON and write it to `path`.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// A single issue inside an `OperationOutcome`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationOutcomeIssue {
+    pub severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<String>,
+}
+
+/// A FHIR `OperationOutcome`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationOutcome {
+    #[serde(default)]
+    pub issue: Vec<OperationOutcomeIssue>,
+}
+
+/// Scan a response payload (a result `Bundle` or a bare `OperationOutcome`) for
+/// any issue of severity `error` or `fatal`, returning their diagnostics.
+///
+/// Returns an empty vec when the batch was accepted cleanly.
+pub fn collect_fatal_issues(response: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let mut visit = |outcome: &Value| {
+        if let Ok(outcome) = serde_json::from_value::<OperationOutcome>(outcome.clone()) {
+            for issue in outcome.issue {
+                if issue.severity == "error" || issue.severity == "fatal" {
+                    problems.push(
+                        issue
+                            .diagnostics
+                            .unwrap_or_else(|| issue.code.unwrap_or_else(|| issue.severity.clone())),
+                    );
+                }
+            }
+        }
+    };
+
+    // A server may answer a transaction with a result Bundle whose entries each
+    // carry a response (sometimes an embedded OperationOutcome), or with a bare
+    // OperationOutcome when the whole transaction was rejected.
+    match response.get("resourceType").and_then(Value::as_str) {
+        Some("Bundle") => {
+            if let Some(entries) = response.get("entry").and_then(Value::as_array) {
+                for entry in entries {
+                    if let Some(resource) = entry.get("resource") {
+                        if resource.get("resourceType").and_then(Value::as_str)
+                            == Some("OperationOutcome")
+                        {
+                            visit(resource);
+                        }
+                    }
+                    if let Some(outcome) = entry
+                        .get("response")
+                        .and_then(|r| r.get("outcome"))
+                    {
+                        visit(outcome);
+                    }
+                }
+            }
+        }
+        Some("OperationOutcome") => visit(response),
+        _ => {}
+    }
+
+    problems
+}
+
+/// POST a transaction bundle to `<base>/` and fail if the server reports any
+/// `error`/`fatal` issue.
+pub async fn post_bundle(base_url: &str, bundle: &Bundle) -> Result<(), Box<dyn Error>> {
+    let url = format!("{}/", base_url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/fhir+json")
+        .header("Accept", "application/fhir+json")
+        .json(bundle)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let body: Value = response.json().await?;
+
+    let problems = collect_fatal_issues(&body);
+    if !problems.is_empty() {
+        return Err(format!(
+            "FHIR server rejected {} entry/entries: {}",
+            problems.len(),
+            problems.join("; ")
+        )
+        .into());
+    }
+    if !status.is_success() {
+        return Err(format!("FHIR server returned HTTP {}", status).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+//! Optional full-text search indexing for generated documents.
+//!
+//! After (or during) generation the same documents can be pushed into a
+//! typo-tolerant search index so the generated dataset is immediately queryable
+//! the way the real app's search screens expect. The index is Meilisearch-style:
+//! documents are added in chunks and each write returns an async task that this
+//! module waits on, so a run does not finish until indexing is confirmed.
+//!
+//! Entities opt in by implementing [`Indexable`], which declares the index name,
+//! the searchable/filterable field layout, and a flat JSON projection keyed by a
+//! stable primary key.
+
+use log::{debug, info};
+use serde::Deserialize;
+use serde_json::Value;
+use std::error::Error;
+use std::time::Duration;
+
+/// Connection settings for the search backend.
+#[derive(Debug, Clone)]
+pub struct IndexerConfig {
+    /// Base URL of the search server, e.g. `http://localhost:7700`.
+    pub url: String,
+    /// Optional override for the index name (defaults to the type's own).
+    pub index_name: Option<String>,
+    /// Optional bearer API key.
+    pub api_key: Option<String>,
+}
+
+/// An entity that can be mirrored into the search index.
+pub trait Indexable {
+    /// The default index (collection) name for this type.
+    fn index_name() -> &'static str;
+
+    /// Fields exposed for full-text, typo-tolerant search, in priority order.
+    fn searchable_attributes() -> Vec<&'static str>;
+
+    /// Fields exposed for filtering and faceting.
+    fn filterable_attributes() -> Vec<&'static str>;
+
+    /// A stable primary key for this document within the index.
+    fn primary_key(&self) -> String;
+
+    /// A flat JSON projection of the searchable/filterable fields plus the
+    /// `id` primary key.
+    fn to_search_document(&self) -> Value;
+}
+
+/// Shape of a Meilisearch async task handle / status response.
+#[derive(Debug, Deserialize)]
+struct TaskInfo {
+    #[serde(rename = "taskUid", alias = "uid")]
+    task_uid: u64,
+    #[serde(default)]
+    status: String,
+}
+
+/// A client over a Meilisearch-style HTTP search server.
+pub struct SearchIndexer {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    index_override: Option<String>,
+}
+
+impl SearchIndexer {
+    /// Build an indexer from the given configuration.
+    pub fn new(config: &IndexerConfig) -> Self {
+        SearchIndexer {
+            client: reqwest::Client::new(),
+            base_url: config.url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            index_override: config.index_name.clone(),
+        }
+    }
+
+    // The effective index name for `T`, honouring any config override.
+    fn index_name<T: Indexable>(&self) -> String {
+        self.index_override
+            .clone()
+            .unwrap_or_else(|| T::index_name().to_string())
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let mut req = self.client.request(method, format!("{}{}", self.base_url, path));
+        if let Some(key) = &self.api_key {
+            req = req.bearer_auth(key);
+        }
+        req
+    }
+
+    /// Declare the searchable and filterable attributes for `T`'s index.
+    pub async fn configure_index<T: Indexable>(
+        &self,
+        index: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        info!("Configuring search index '{}'", index);
+        let settings = serde_json::json!({
+            "searchableAttributes": T::searchable_attributes(),
+            "filterableAttributes": T::filterable_attributes(),
+        });
+        let task: TaskInfo = self
+            .request(reqwest::Method::PATCH, &format!("/indexes/{}/settings", index))
+            .json(&settings)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        self.wait_for_task(task.task_uid).await
+    }
+
+    /// Index `items` in 100-document chunks, waiting on each write task so the
+    /// call only returns once the backend confirms the documents are searchable.
+    pub async fn index_documents<T: Indexable>(
+        &self,
+        items: &[T],
+    ) -> Result<(), Box<dyn Error>> {
+        let index = self.index_name::<T>();
+        self.configure_index::<T>(&index).await?;
+
+        let chunk_size = 100;
+        for (chunk_idx, chunk) in items.chunks(chunk_size).enumerate() {
+            let docs: Vec<Value> = chunk.iter().map(|item| item.to_search_document()).collect();
+            let task: TaskInfo = self
+                .request(
+                    reqwest::Method::POST,
+                    &format!("/indexes/{}/documents?primaryKey=id", index),
+                )
+                .json(&docs)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            debug!(
+                "Submitted index chunk {} ({} docs), task {}",
+                chunk_idx + 1,
+                docs.len(),
+                task.task_uid
+            );
+            self.wait_for_task(task.task_uid).await?;
+        }
+
+        info!("Indexed {} document(s) into '{}'", items.len(), index);
+        Ok(())
+    }
+
+    /// Poll a task until it reaches a terminal state, failing on `failed`.
+    async fn wait_for_task(&self, task_uid: u64) -> Result<(), Box<dyn Error>> {
+        loop {
+            let task: TaskInfo = self
+                .request(reqwest::Method::GET, &format!("/tasks/{}", task_uid))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            match task.status.as_str() {
+                "succeeded" => return Ok(()),
+                "failed" | "canceled" => {
+                    return Err(format!("index task {} {}", task_uid, task.status).into());
+                }
+                _ => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Database housekeeping shared by the `indexes`, `repair`, and `purge`
+//! subcommands of `medapp-gen`.
+
+use futures::StreamExt;
+use log::{info, warn};
+use mongodb::bson::{doc, oid::ObjectId, Document};
+use mongodb::options::IndexOptions;
+use mongodb::{Database, IndexModel};
+use std::collections::HashSet;
+use std::error::Error;
+
+/// Create the indexes every collection relies on for uniqueness and lookups.
+pub async fn create_indexes(db: &Database) -> Result<(), Box<dyn Error>> {
+    info!("Creating indexes for patients collection");
+    let patients_collection = db.collection::<Document>("patients");
+    let patient_indexes = vec![
+        IndexModel::builder()
+            .keys(doc! { "email": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "numeroSecuriteSociale": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+    ];
+    patients_collection
+        .create_indexes(patient_indexes, None)
+        .await?;
+
+    info!("Creating indexes for medecins collection");
+    let medecins_collection = db.collection::<Document>("medecins");
+    let medecin_indexes = vec![
+        IndexModel::builder()
+            .keys(doc! { "email": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "numeroOrdre": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+    ];
+    medecins_collection
+        .create_indexes(medecin_indexes, None)
+        .await?;
+
+    info!("Creating indexes for radiologues collection");
+    let radiologues_collection = db.collection::<Document>("radiologues");
+    let radiologue_indexes = vec![
+        IndexModel::builder()
+            .keys(doc! { "email": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+        IndexModel::builder()
+            .keys(doc! { "numeroOrdre": 1 })
+            .options(IndexOptions::builder().unique(true).build())
+            .build(),
+    ];
+    radiologues_collection
+        .create_indexes(radiologue_indexes, None)
+        .await?;
+
+    info!("Creating indexes for reports collection");
+    let reports_collection = db.collection::<Document>("reports");
+    let report_indexes = vec![
+        IndexModel::builder().keys(doc! { "patient_id": 1 }).build(),
+        IndexModel::builder()
+            .keys(doc! { "radiologue_id": 1 })
+            .build(),
+        IndexModel::builder().keys(doc! { "medecin_id": 1 }).build(),
+    ];
+    reports_collection
+        .create_indexes(report_indexes, None)
+        .await?;
+
+    info!("Creating indexes for ordonnances collection");
+    let ordonnances_collection = db.collection::<Document>("ordonnances");
+    let ordonnance_indexes = vec![
+        IndexModel::builder().keys(doc! { "patient_id": 1 }).build(),
+        IndexModel::builder().keys(doc! { "medecin_id": 1 }).build(),
+    ];
+    ordonnances_collection
+        .create_indexes(ordonnance_indexes, None)
+        .await?;
+
+    info!("All indexes created successfully");
+    Ok(())
+}
+
+// Collect the set of `_id`s present in a collection.
+async fn existing_ids(db: &Database, collection: &str) -> Result<HashSet<ObjectId>, Box<dyn Error>> {
+    let coll = db.collection::<Document>(collection);
+    let mut cursor = coll.find(None, None).await?;
+    let mut ids = HashSet::new();
+    while let Some(result) = cursor.next().await {
+        if let Ok(id) = result?.get_object_id("_id") {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Delete documents whose reference fields point at documents that no longer
+/// exist, returning the number removed.
+///
+/// This is the inverse of the `--orphan-rate` injection: it cleans up the
+/// dangling references that negative-testing runs leave behind.
+pub async fn repair_dangling(db: &Database) -> Result<u64, Box<dyn Error>> {
+    let patients = existing_ids(db, "patients").await?;
+    let radiologues = existing_ids(db, "radiologues").await?;
+    let doctors = existing_ids(db, "doctors").await?;
+
+    let mut removed = 0u64;
+
+    // Reports reference a patient, a radiologist, and a doctor.
+    let reports = db.collection::<Document>("reports");
+    let mut cursor = reports.find(None, None).await?;
+    while let Some(result) = cursor.next().await {
+        let doc = result?;
+        let dangling = doc
+            .get_object_id("patient_id")
+            .map(|id| !patients.contains(&id))
+            .unwrap_or(true)
+            || doc
+                .get_object_id("radiologist_id")
+                .map(|id| !radiologues.contains(&id))
+                .unwrap_or(true)
+            || doc
+                .get_object_id("doctor_id")
+                .map(|id| !doctors.contains(&id))
+                .unwrap_or(true);
+        if dangling {
+            if let Ok(id) = doc.get_object_id("_id") {
+                reports.delete_one(doc! { "_id": id }, None).await?;
+                removed += 1;
+            }
+        }
+    }
+
+    // Prescriptions reference a patient and a doctor.
+    let ordonnances = db.collection::<Document>("ordonnances");
+    let mut cursor = ordonnances.find(None, None).await?;
+    while let Some(result) = cursor.next().await {
+        let doc = result?;
+        let dangling = doc
+            .get_object_id("patient_id")
+            .map(|id| !patients.contains(&id))
+            .unwrap_or(true)
+            || doc
+                .get_object_id("doctor_id")
+                .map(|id| !doctors.contains(&id))
+                .unwrap_or(true);
+        if dangling {
+            if let Ok(id) = doc.get_object_id("_id") {
+                ordonnances.delete_one(doc! { "_id": id }, None).await?;
+                removed += 1;
+            }
+        }
+    }
+
+    info!("Removed {} document(s) with dangling references", removed);
+    Ok(removed)
+}
+
+/// Drop the given collections (all generated collections when `collections` is
+/// empty).
+pub async fn purge(db: &Database, collections: &[String]) -> Result<(), Box<dyn Error>> {
+    let targets: Vec<String> = if collections.is_empty() {
+        ["patients", "radiologues", "doctors", "reports", "ordonnances"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    } else {
+        collections.to_vec()
+    };
+
+    for name in targets {
+        warn!("Dropping collection '{}'", name);
+        db.collection::<Document>(&name).drop(None).await?;
+    }
+
+    info!("Purge complete");
+    Ok(())
+}
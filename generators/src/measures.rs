@@ -0,0 +1,92 @@
+//! Declarative clinical quality-measure definitions that drive generation.
+//!
+//! Instead of purely random `diagnosis`/`medications` fields, generation can be
+//! steered by a measure definition loaded from JSON (e.g. "patients with a
+//! Type 2 diabetes diagnosis who also have a Metformin prescription within 90
+//! days"). A definition parses into a population spec — an initial population, a
+//! denominator filter, and a numerator condition expressed over entity fields
+//! and cross-collection links — which the `measures` binary uses to produce a
+//! coherent, cross-referenced set of patients, ordonnances, and reports where a
+//! configurable fraction land in the numerator.
+//!
+//! The generated [`MeasureSummary`] records how many records fall in each bucket
+//! so the dataset can validate a downstream measure-calculation engine against
+//! known-correct expected counts.
+
+use serde::Deserialize;
+use std::error::Error;
+use std::path::Path;
+
+/// A parsed clinical quality-measure definition.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeasureDefinition {
+    /// Stable identifier of the measure.
+    pub id: String,
+    /// Human-readable title.
+    pub title: String,
+    /// Constraints on the initial patient population.
+    #[serde(default)]
+    pub initial_population: PopulationSpec,
+    /// Condition a patient must meet to enter the denominator.
+    pub denominator: Condition,
+    /// Condition a denominator patient must meet to enter the numerator.
+    pub numerator: Condition,
+}
+
+/// Constraints on who enters the initial population.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PopulationSpec {
+    /// Minimum patient age in years.
+    #[serde(default)]
+    pub min_age: Option<u32>,
+    /// Maximum patient age in years.
+    #[serde(default)]
+    pub max_age: Option<u32>,
+}
+
+/// A condition over entity fields and cross-collection links.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Condition {
+    /// Required prescription diagnosis, e.g. `"Type 2 diabetes"`.
+    #[serde(default)]
+    pub diagnosis: Option<String>,
+    /// Required prescribed medication, e.g. `"Metformin"`.
+    #[serde(default)]
+    pub medication: Option<String>,
+    /// Time window (in days from the patient's index event) the linked record
+    /// must fall within.
+    #[serde(default)]
+    pub within_days: Option<i64>,
+    /// Whether a linked radiology report is required.
+    #[serde(default)]
+    pub requires_report: bool,
+    /// Required report exam type, when a report is required.
+    #[serde(default)]
+    pub report_exam_type: Option<String>,
+}
+
+impl MeasureDefinition {
+    /// Load and parse a measure definition from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let raw = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&raw)?)
+    }
+}
+
+/// Counts of generated records in each measure bucket.
+#[derive(Debug, Clone, Default)]
+pub struct MeasureSummary {
+    /// Patients in the initial population.
+    pub initial_population: usize,
+    /// Patients meeting the denominator condition.
+    pub denominator: usize,
+    /// Denominator patients that also meet the numerator condition.
+    pub numerator: usize,
+}
+
+impl MeasureSummary {
+    /// Denominator patients that are not in the numerator.
+    pub fn denominator_only(&self) -> usize {
+        self.denominator.saturating_sub(self.numerator)
+    }
+}
@@ -0,0 +1,203 @@
+//! Optional Prometheus observability for generation runs.
+//!
+//! When the `--metrics-addr <host:port>` flag is passed, a [`Metrics`] handle is
+//! threaded through [`crate::insert_batches`] and [`crate::generate_in_batches`]
+//! and a tiny HTTP server is [`serve`]d in the background. The handle records
+//! counters (documents generated per collection, batches inserted, failed
+//! inserts) and a per-collection latency histogram; on every `/metrics` scrape
+//! the latency samples are drained into min/p50/p90/p99/max summary buckets and
+//! rendered alongside a live gauge for the current in-flight batch size. This
+//! turns the one-off generators into something you can profile while loading
+//! millions of rows, so batch size and pool limits stop being guesses.
+
+use crate::bench::LatencySummary;
+use log::{error, info};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// Per-collection accumulators. Latency samples are buffered until a scrape
+// drains them into summary buckets, keeping the hot path a single push.
+#[derive(Default)]
+struct CollectionStats {
+    docs_generated: u64,
+    batches_inserted: u64,
+    failed_inserts: u64,
+    latency_ms: Vec<f64>,
+}
+
+#[derive(Default)]
+struct Inner {
+    collections: Mutex<BTreeMap<String, CollectionStats>>,
+    in_flight_batch: AtomicU64,
+}
+
+/// A cloneable handle to the generation metrics.
+///
+/// Cloning shares the same underlying counters, so the background HTTP server
+/// and the generation loop observe one another's updates.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Inner>,
+}
+
+impl Metrics {
+    /// Create an empty metrics registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Mutate the stats for one collection, inserting an empty entry on first use.
+    fn with_collection<F: FnOnce(&mut CollectionStats)>(&self, collection: &str, f: F) {
+        let mut collections = self.inner.collections.lock().unwrap();
+        f(collections.entry(collection.to_string()).or_default());
+    }
+
+    /// Record that `n` documents were generated for `collection`.
+    pub fn record_generated(&self, collection: &str, n: u64) {
+        self.with_collection(collection, |s| s.docs_generated += n);
+    }
+
+    /// Record a successful batch insert taking `latency_ms` milliseconds.
+    pub fn record_batch_insert(&self, collection: &str, latency_ms: f64) {
+        self.with_collection(collection, |s| {
+            s.batches_inserted += 1;
+            s.latency_ms.push(latency_ms);
+        });
+    }
+
+    /// Record a batch insert that failed for `collection`.
+    pub fn record_failed_insert(&self, collection: &str) {
+        self.with_collection(collection, |s| s.failed_inserts += 1);
+    }
+
+    /// Set the current in-flight batch size gauge.
+    pub fn set_in_flight_batch(&self, size: u64) {
+        self.inner.in_flight_batch.store(size, Ordering::Relaxed);
+    }
+
+    /// Render the current state in Prometheus text exposition format, draining
+    /// the per-collection latency samples into summary buckets.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP medapp_docs_generated_total Documents generated per collection.\n");
+        out.push_str("# TYPE medapp_docs_generated_total counter\n");
+        out.push_str("# HELP medapp_batches_inserted_total Batches acknowledged per collection.\n");
+        out.push_str("# TYPE medapp_batches_inserted_total counter\n");
+        out.push_str("# HELP medapp_failed_inserts_total Failed batch inserts per collection.\n");
+        out.push_str("# TYPE medapp_failed_inserts_total counter\n");
+        out.push_str("# HELP medapp_insert_latency_ms Batch insert latency summary per collection.\n");
+        out.push_str("# TYPE medapp_insert_latency_ms gauge\n");
+
+        let mut collections = self.inner.collections.lock().unwrap();
+        for (name, stats) in collections.iter_mut() {
+            let _ = writeln!(
+                out,
+                "medapp_docs_generated_total{{collection=\"{}\"}} {}",
+                name, stats.docs_generated
+            );
+            let _ = writeln!(
+                out,
+                "medapp_batches_inserted_total{{collection=\"{}\"}} {}",
+                name, stats.batches_inserted
+            );
+            let _ = writeln!(
+                out,
+                "medapp_failed_inserts_total{{collection=\"{}\"}} {}",
+                name, stats.failed_inserts
+            );
+
+            // Drain the accumulated samples into the summary buckets.
+            let summary = LatencySummary::from_samples(&stats.latency_ms);
+            stats.latency_ms.clear();
+            for (bucket, value) in [
+                ("min", summary.min),
+                ("p50", summary.p50),
+                ("p90", summary.p90),
+                ("p99", summary.p99),
+                ("max", summary.max),
+            ] {
+                let _ = writeln!(
+                    out,
+                    "medapp_insert_latency_ms{{collection=\"{}\",bucket=\"{}\"}} {}",
+                    name, bucket, value
+                );
+            }
+        }
+        drop(collections);
+
+        out.push_str("# HELP medapp_in_flight_batch Documents in the batch currently being inserted.\n");
+        out.push_str("# TYPE medapp_in_flight_batch gauge\n");
+        let _ = writeln!(
+            out,
+            "medapp_in_flight_batch {}",
+            self.inner.in_flight_batch.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+/// Spawn a background HTTP server that serves [`Metrics::render`] at `/metrics`.
+///
+/// The listener is bound before returning so a bind failure surfaces to the
+/// caller; the accept loop then runs detached for the lifetime of the process.
+pub async fn serve(metrics: Metrics, addr: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    info!("Serving Prometheus metrics on http://{}/metrics", local);
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let metrics = metrics.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, &metrics).await {
+                            error!("metrics connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => error!("metrics accept error: {}", e),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// Read the request line, answer `/metrics` with the exposition payload and any
+// other path with 404. This is a deliberately minimal HTTP/1.1 responder — it
+// depends only on tokio, which the rest of the crate already uses.
+async fn handle_connection(
+    mut stream: tokio::net::TcpStream,
+    metrics: &Metrics,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/");
+
+    let response = if path == "/metrics" {
+        let body = metrics.render();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
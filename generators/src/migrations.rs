@@ -0,0 +1,290 @@
+//! Schema evolution for the generator collections.
+//!
+//! This module holds two deliberately separate migration mechanisms; keep them
+//! distinct rather than conflating their vocabulary:
+//!
+//! * **Document-version migrations** — [`Migration`] / [`all`], driven by the
+//!   standalone `migrate` binary. Every generated document carries a
+//!   `schema_version` field (see [`CURRENT_SCHEMA_VERSION`]); each `Migration`
+//!   is a per-document `up` transform that rewrites below-version documents up
+//!   to the current version (e.g. consolidating `doctors`/`patients`/
+//!   `radiologues` into one polymorphic `entities` collection). `version` here
+//!   means a document schema version.
+//!
+//! * **Operational migrations** — [`OperationalMigration`] /
+//!   [`operational_migrations`] / [`run_operational`], driven by the
+//!   `medapp-gen db-migrate` subcommand. Each is a named, numbered change to
+//!   the database itself (create an index, rename a field), applied once in
+//!   order and recorded in [`MIGRATIONS_COLLECTION`]. `version` here is an
+//!   operational migration number tracked in `_migrations`, unrelated to a
+//!   document's `schema_version`.
+
+use futures::StreamExt;
+use log::{info, warn};
+use mongodb::bson::{doc, DateTime as BsonDateTime, Document};
+use mongodb::options::IndexOptions;
+use mongodb::{Client, Database, IndexModel};
+use std::collections::HashSet;
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The latest schema version. Keep this equal to the highest migration version.
+pub const CURRENT_SCHEMA_VERSION: i32 = 4;
+
+/// Version stamped on freshly generated documents, before any document-version
+/// migration has run.
+///
+/// Generators write the pre-consolidation shape — separate `patients`,
+/// `radiologues`, and `doctors` collections with no `kind` discriminator — so
+/// their documents start below migration 1. The [`all`] chain then advances
+/// them into the consolidated `entities` collection and up to
+/// [`CURRENT_SCHEMA_VERSION`]; stamping them at the current version instead
+/// would mislabel them as already-consolidated and make `migrate` a no-op.
+pub const INITIAL_SCHEMA_VERSION: i32 = 0;
+
+/// A single schema migration.
+pub struct Migration {
+    /// Strictly increasing version this migration brings a document up to.
+    pub version: i32,
+    /// Collection whose documents this migration scans.
+    pub collection: &'static str,
+    /// Short human-readable description, shown by the `migrate` runner.
+    pub description: &'static str,
+    /// Idempotent transform applied to each below-version document.
+    pub up: fn(Document) -> Document,
+    /// When set, move the transformed document into this collection instead of
+    /// writing it back in place (used for collection consolidation).
+    pub target_collection: Option<&'static str>,
+}
+
+/// The ordered list of all migrations.
+///
+/// Versions must be contiguous and strictly increasing; use a no-op `up` to
+/// reserve a version without changing any document.
+pub fn all() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            collection: "patients",
+            description: "consolidate patients into the polymorphic `entities` collection",
+            up: |doc| tag_kind(doc, "patient"),
+            target_collection: Some("entities"),
+        },
+        Migration {
+            version: 2,
+            collection: "radiologues",
+            description: "consolidate radiologues into the polymorphic `entities` collection",
+            up: |doc| tag_kind(doc, "radiologue"),
+            target_collection: Some("entities"),
+        },
+        Migration {
+            version: 3,
+            collection: "doctors",
+            description: "consolidate doctors into the polymorphic `entities` collection",
+            up: |doc| tag_kind(doc, "doctor"),
+            target_collection: Some("entities"),
+        },
+        Migration {
+            version: 4,
+            collection: "entities",
+            description: "no-op placeholder to keep the version sequence contiguous",
+            up: |doc| doc,
+            target_collection: None,
+        },
+    ]
+}
+
+/// Stamp a `kind` discriminator on a document, leaving it untouched if already
+/// set (idempotent).
+fn tag_kind(mut doc: Document, kind: &str) -> Document {
+    if !doc.contains_key("kind") {
+        doc.insert("kind", kind);
+    }
+    doc
+}
+
+/// Collection that records which [`OperationalMigration`]s have been applied.
+pub const MIGRATIONS_COLLECTION: &str = "_migrations";
+
+/// Result of a [`OperationalMigration`]'s `up` step.
+///
+/// The bound is `Send + Sync` so the boxed future can be awaited from the
+/// multi-threaded runtime; [`run_operational`] folds it back into the
+/// crate-wide `Box<dyn Error>` at the call boundary.
+pub type MigrationResult = Result<(), Box<dyn Error + Send + Sync>>;
+
+type MigrationFuture = Pin<Box<dyn Future<Output = MigrationResult> + Send>>;
+
+/// A named, numbered operational migration run against the live database.
+///
+/// Where a [`Migration`] rewrites already-generated documents up to
+/// [`CURRENT_SCHEMA_VERSION`], an `OperationalMigration` changes the database
+/// itself — creating an index, renaming a field across a collection, and so on.
+/// The [`run_operational`] runner applies the not-yet-applied migrations in
+/// strict numeric order and records each in [`MIGRATIONS_COLLECTION`], so the
+/// index creation the `indexes` subcommand performs as a one-shot can instead
+/// evolve safely across environments.
+pub struct OperationalMigration {
+    /// Strictly increasing version; applied lowest-first.
+    pub version: i32,
+    /// Stable identifier, e.g. `001_add_patient_email_index`.
+    pub name: &'static str,
+    /// Idempotent change applied to the database.
+    pub up: fn(Database) -> MigrationFuture,
+}
+
+/// The ordered list of operational schema migrations.
+///
+/// Append new entries with the next contiguous version; never renumber or edit
+/// an already-released migration, as its version may already be recorded in a
+/// deployed `_migrations` collection.
+pub fn operational_migrations() -> Vec<OperationalMigration> {
+    vec![
+        OperationalMigration {
+            version: 1,
+            name: "001_add_patient_email_index",
+            up: |db| Box::pin(add_patient_email_index(db)),
+        },
+        OperationalMigration {
+            version: 2,
+            name: "002_rename_phoneNumber_to_telephone",
+            up: |db| Box::pin(rename_phone_number_to_telephone(db)),
+        },
+    ]
+}
+
+// 001: the unique `email` index that `create_indexes` also creates one-shot.
+async fn add_patient_email_index(db: Database) -> MigrationResult {
+    let patients = db.collection::<Document>("patients");
+    let index = IndexModel::builder()
+        .keys(doc! { "email": 1 })
+        .options(IndexOptions::builder().unique(true).build())
+        .build();
+    patients.create_index(index, None).await?;
+    Ok(())
+}
+
+// 002: rename the legacy `phoneNumber` field to `telephone` on every patient.
+async fn rename_phone_number_to_telephone(db: Database) -> MigrationResult {
+    let patients = db.collection::<Document>("patients");
+    patients
+        .update_many(
+            doc! { "phoneNumber": { "$exists": true } },
+            doc! { "$rename": { "phoneNumber": "telephone" } },
+            None,
+        )
+        .await?;
+    Ok(())
+}
+
+/// What the runner decided to do with a single migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationOutcome {
+    /// Applied now (or, under `--dry-run`, would have been).
+    Applied,
+    /// Already recorded in [`MIGRATIONS_COLLECTION`] from an earlier run.
+    AlreadyApplied,
+    /// Above the requested `--target` and left for later.
+    Skipped,
+}
+
+/// Apply every pending [`OperationalMigration`] up to `target` (all of them when
+/// `None`) in strict numeric order.
+///
+/// Applied versions are read from and written to [`MIGRATIONS_COLLECTION`], so
+/// the runner is safe to re-run and resilient to partially-applied state: an
+/// already-recorded version is skipped, and a gap — a lower version missing
+/// while a higher one is present — is filled on the next run rather than
+/// silently ignored. With `dry_run` the plan is logged but nothing changes.
+pub async fn run_operational(
+    db: &Database,
+    client: &Client,
+    target: Option<i32>,
+    dry_run: bool,
+) -> Result<Vec<(i32, MigrationOutcome)>, Box<dyn Error>> {
+    let mut migrations = operational_migrations();
+    migrations.sort_by_key(|m| m.version);
+
+    let applied = applied_versions(db).await?;
+    let mut outcomes = Vec::with_capacity(migrations.len());
+    let mut applied_now = 0u32;
+
+    for migration in &migrations {
+        if target.map(|t| migration.version > t).unwrap_or(false) {
+            outcomes.push((migration.version, MigrationOutcome::Skipped));
+            continue;
+        }
+        if applied.contains(&migration.version) {
+            outcomes.push((migration.version, MigrationOutcome::AlreadyApplied));
+            continue;
+        }
+        if dry_run {
+            info!(
+                "[dry-run] would apply migration {:03} {}",
+                migration.version, migration.name
+            );
+        } else {
+            info!("Applying migration {:03} {}", migration.version, migration.name);
+            apply_one(db, client, migration).await?;
+        }
+        applied_now += 1;
+        outcomes.push((migration.version, MigrationOutcome::Applied));
+    }
+
+    if applied_now == 0 {
+        warn!("No pending migrations; database already at the requested version");
+    } else if !dry_run {
+        info!("Applied {} migration(s)", applied_now);
+    }
+    Ok(outcomes)
+}
+
+// Run a single migration, then record it. The record write is wrapped in a
+// transaction where the deployment supports sessions (replica sets); on a
+// standalone server the insert is performed directly.
+async fn apply_one(
+    db: &Database,
+    client: &Client,
+    migration: &OperationalMigration,
+) -> Result<(), Box<dyn Error>> {
+    (migration.up)(db.clone()).await?;
+
+    let record = doc! {
+        "version": migration.version,
+        "name": migration.name,
+        "applied_at": BsonDateTime::now(),
+    };
+    let collection = db.collection::<Document>(MIGRATIONS_COLLECTION);
+
+    match client.start_session(None).await {
+        Ok(mut session) => {
+            session.start_transaction(None).await?;
+            if let Err(e) = collection
+                .insert_one_with_session(&record, None, &mut session)
+                .await
+            {
+                session.abort_transaction().await?;
+                return Err(Box::new(e));
+            }
+            session.commit_transaction().await?;
+        }
+        Err(_) => {
+            collection.insert_one(&record, None).await?;
+        }
+    }
+    Ok(())
+}
+
+// The set of versions already recorded in the `_migrations` collection.
+async fn applied_versions(db: &Database) -> Result<HashSet<i32>, Box<dyn Error>> {
+    let collection = db.collection::<Document>(MIGRATIONS_COLLECTION);
+    let mut cursor = collection.find(None, None).await?;
+    let mut versions = HashSet::new();
+    while let Some(result) = cursor.next().await {
+        if let Ok(version) = result?.get_i32("version") {
+            versions.insert(version);
+        }
+    }
+    Ok(versions)
+}